@@ -23,7 +23,524 @@ use snarkvm_utilities::{
     to_bytes,
 };
 
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use parking_lot::RwLock;
+use rand::seq::SliceRandom;
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The number of pending blocks the import queue will buffer before applying backpressure
+/// to the networking task that is feeding it.
+const IMPORT_QUEUE_BOUND: usize = 256;
+
+/// The number of most-recent inventory items (block and transaction hashes) remembered
+/// per peer before the oldest entries are evicted.
+const KNOWN_INVENTORY_CAPACITY: usize = 1024;
+
+/// The number of peers to send the full transaction to out of `eligible_count` candidates,
+/// bounding gossip amplification to roughly sqrt(N) while still reaching at least one peer.
+fn fanout_size(eligible_count: usize) -> usize {
+    (eligible_count as f64).sqrt().ceil().max(1.0) as usize
+}
+
+/// A bounded, FIFO-evicted set of inventory hashes known to be held by a single peer,
+/// used to avoid re-announcing items it has already seen.
+#[derive(Default)]
+struct KnownInventory {
+    order: VecDeque<Vec<u8>>,
+    items: HashSet<Vec<u8>>,
+}
+
+impl KnownInventory {
+    /// Returns `true` if the peer is already known to hold `item`.
+    fn contains(&self, item: &[u8]) -> bool {
+        self.items.contains(item)
+    }
+
+    /// Records that the peer now holds `item`, evicting the oldest entry if the
+    /// bound has been exceeded.
+    fn insert(&mut self, item: Vec<u8>) {
+        if self.items.insert(item.clone()) {
+            self.order.push_back(item);
+
+            if self.order.len() > KNOWN_INVENTORY_CAPACITY {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.items.remove(&oldest);
+                }
+            }
+        }
+    }
+}
+
+/// Requests the current snapshot manifest from a peer.
+#[derive(Clone)]
+pub struct GetSnapshotManifest;
+
+/// Lists the chunk hashes that make up a snapshot, and the block height/hash it was taken at.
+#[derive(Clone)]
+pub struct SnapshotManifest {
+    pub block_height: u32,
+    pub block_hash: BlockHeaderHash,
+    pub chunk_hashes: Vec<Vec<u8>>,
+}
+
+impl SnapshotManifest {
+    pub fn new(block_height: u32, block_hash: BlockHeaderHash, chunk_hashes: Vec<Vec<u8>>) -> Self {
+        Self {
+            block_height,
+            block_hash,
+            chunk_hashes,
+        }
+    }
+}
+
+/// Requests a single snapshot chunk, identified by its hash from the manifest.
+#[derive(Clone)]
+pub struct GetSnapshotChunk {
+    pub chunk_hash: Vec<u8>,
+}
+
+impl GetSnapshotChunk {
+    pub fn new(chunk_hash: Vec<u8>) -> Self {
+        Self { chunk_hash }
+    }
+}
+
+/// A single chunk of snapshot state, identified by the hash advertised in the manifest.
+#[derive(Clone)]
+pub struct SnapshotChunk {
+    pub chunk_hash: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+impl SnapshotChunk {
+    pub fn new(chunk_hash: Vec<u8>, data: Vec<u8>) -> Self {
+        Self { chunk_hash, data }
+    }
+}
+
+/// Hashes snapshot chunk data so it can be checked against the hash advertised in the manifest.
+fn hash_snapshot_chunk(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+}
+
+/// A lightweight notice that the sender has a transaction, sent to peers excluded from the
+/// sqrt(N) fan-out in `propagate_transaction` so the network-wide propagation guarantee still
+/// holds without paying the bandwidth cost of pushing the full transaction to everyone.
+#[derive(Clone)]
+pub struct TransactionAnnounce {
+    pub transaction_id: Vec<u8>,
+}
+
+impl TransactionAnnounce {
+    pub fn new(transaction_id: Vec<u8>) -> Self {
+        Self { transaction_id }
+    }
+}
+
+/// This node's own snapshot data, served to peers requesting `GetSnapshotManifest` and
+/// `GetSnapshotChunk`, and the landing spot for chunks restored from a peer's snapshot.
+/// Starts empty; a snapshot producer populates `manifest`/`chunks` periodically.
+#[derive(Default)]
+struct SnapshotStore {
+    manifest: Option<SnapshotManifest>,
+    chunks: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl SnapshotStore {
+    fn manifest(&self) -> Option<SnapshotManifest> {
+        self.manifest.clone()
+    }
+
+    fn chunk(&self, chunk_hash: &[u8]) -> Option<Vec<u8>> {
+        self.chunks.get(chunk_hash).cloned()
+    }
+
+    fn apply_chunk(&mut self, chunk_hash: Vec<u8>, data: Vec<u8>) {
+        self.chunks.insert(chunk_hash, data);
+    }
+}
+
+/// How long to wait for a `SnapshotManifest` response before giving up on snapshot
+/// sync for this attempt and falling back to full block-by-block sync.
+const SNAPSHOT_MANIFEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Tracks a snapshot restore in progress: the manifest being followed, the peer
+/// that supplied it, and which of its chunks have already been verified and applied.
+#[derive(Default)]
+struct SnapshotSyncState {
+    manifest: Option<SnapshotManifest>,
+    sync_node: Option<SocketAddr>,
+    restored_chunks: HashSet<Vec<u8>>,
+    requested: bool,
+    requested_at: Option<Instant>,
+}
+
+impl SnapshotSyncState {
+    fn is_complete(&self) -> bool {
+        self.manifest
+            .as_ref()
+            .map(|manifest| {
+                !manifest.chunk_hashes.is_empty()
+                    && manifest.chunk_hashes.iter().all(|hash| self.restored_chunks.contains(hash))
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Requests a peer's block header hashes, walking backward from `from_height`.
+/// Used to locate the common ancestor once a divergence from a peer is detected.
+#[derive(Clone)]
+pub struct GetHeaders {
+    pub from_height: u32,
+}
+
+impl GetHeaders {
+    pub fn new(from_height: u32) -> Self {
+        Self { from_height }
+    }
+}
+
+/// A batch of header hashes, walking backward from `from_height` in descending order.
+#[derive(Clone)]
+pub struct Headers {
+    pub from_height: u32,
+    pub header_hashes: Vec<BlockHeaderHash>,
+}
+
+impl Headers {
+    pub fn new(from_height: u32, header_hashes: Vec<BlockHeaderHash>) -> Self {
+        Self {
+            from_height,
+            header_hashes,
+        }
+    }
+}
+
+/// How long to wait for a response to a `GetBlock` request before retrying it against
+/// a different peer.
+const SYNC_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How many times in total a `GetBlock` request is sent (the first request plus its retries
+/// against different peers) before the block is given up on as unavailable.
+const SYNC_REQUEST_MAX_ATTEMPTS: usize = 3;
+
+/// The number of header hashes requested per `GetHeaders` round when walking backward
+/// to locate a common ancestor with a diverged peer.
+const HEADER_WALK_BATCH: u32 = 128;
+
+/// How many times in a row a peer may answer `GetHeaders` with an empty `Headers` reply
+/// before we give up walking back with it, so a peer that can't make progress (or won't)
+/// doesn't get asked the identical question forever.
+const HEADER_WALK_MAX_EMPTY_REPLIES: usize = SYNC_REQUEST_MAX_ATTEMPTS;
+
+/// The height to resume the backward header walk from, given the last batch's starting
+/// height and how many headers it contained. An empty batch leaves the height unchanged,
+/// which is why callers must cap retries against a peer that keeps replying empty.
+fn next_header_walk_height(from_height: u32, headers_received: usize) -> u32 {
+    from_height.saturating_sub(headers_received as u32)
+}
+
+/// The peer and timing metadata tracked for a single outstanding `GetBlock` request.
+struct OutstandingRequest {
+    peer: SocketAddr,
+    sent_at: Instant,
+    attempts: usize,
+}
+
+impl OutstandingRequest {
+    /// Returns `true` once this request has been sent `SYNC_REQUEST_MAX_ATTEMPTS` times in
+    /// total (the first request plus its retries) and should be given up on rather than
+    /// retried again.
+    fn is_exhausted(&self) -> bool {
+        self.attempts >= SYNC_REQUEST_MAX_ATTEMPTS
+    }
+}
+
+/// Tracks outstanding `GetBlock` requests issued during sync, so a peer that silently
+/// drops one doesn't stall sync indefinitely. Requests that exceed `SYNC_REQUEST_TIMEOUT`
+/// are retried against a different peer, up to `SYNC_REQUEST_MAX_ATTEMPTS`.
+#[derive(Default)]
+struct SyncRequester {
+    outstanding: RwLock<HashMap<BlockHeaderHash, OutstandingRequest>>,
+}
+
+impl SyncRequester {
+    /// Records that `block_hash` was just requested from `peer`.
+    fn track(&self, block_hash: BlockHeaderHash, peer: SocketAddr) {
+        self.outstanding.write().insert(
+            block_hash,
+            OutstandingRequest {
+                peer,
+                sent_at: Instant::now(),
+                attempts: 1,
+            },
+        );
+    }
+
+    /// Removes the outstanding request for `block_hash`, now that it has arrived.
+    fn resolve(&self, block_hash: &BlockHeaderHash) {
+        self.outstanding.write().remove(block_hash);
+    }
+
+    /// Re-issues requests that have exceeded the timeout against a peer other than the
+    /// one that failed to respond, and drops requests that have exhausted their attempts.
+    async fn retry_timeouts(&self, outbound: &Outbound, connected_peers: &HashMap<SocketAddr, PeerInfo>) {
+        let mut retries = vec![];
+        let mut unavailable = vec![];
+
+        {
+            let mut outstanding = self.outstanding.write();
+            outstanding.retain(|block_hash, request| {
+                if request.sent_at.elapsed() < SYNC_REQUEST_TIMEOUT {
+                    return true;
+                }
+
+                if request.is_exhausted() {
+                    unavailable.push(*block_hash);
+                    return false;
+                }
+
+                match connected_peers.keys().find(|addr| **addr != request.peer).copied() {
+                    Some(next_peer) => {
+                        request.peer = next_peer;
+                        request.sent_at = Instant::now();
+                        request.attempts += 1;
+                        retries.push((*block_hash, next_peer));
+                    }
+                    // No other peer to retry against yet; leave it pending and try again later.
+                    None => request.sent_at = Instant::now(),
+                }
+
+                true
+            });
+        }
+
+        for block_hash in &unavailable {
+            warn!("Giving up on block {} after repeated timeouts", hex::encode(block_hash.0));
+        }
+
+        for (block_hash, peer) in retries {
+            debug!("Retrying GetBlock for {} against {}", hex::encode(block_hash.0), peer);
+            outbound.broadcast(&Request::GetBlock(peer, GetBlock::new(block_hash))).await;
+        }
+    }
+}
+
+/// A block more than this many heights below the current chain height is treated as a
+/// historical backfill block rather than one near the live tip.
+const ANCIENT_BLOCK_THRESHOLD: u32 = 100;
+
+/// Which `ImportQueue` lane a block should be verified on: the low-latency tip lane,
+/// or the lower-priority ancient lane used for bulk backfill during a catch-up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportLane {
+    /// The block is at or near the live chain tip; verify and propagate it immediately.
+    Tip,
+    /// The block is far below the current height; verify it without delaying tip traffic.
+    Ancient,
+}
+
+/// Decides which `ImportQueue` lane a block belongs on, given the current chain height and
+/// the height of its parent (`None` if the parent isn't in storage, e.g. it diverged from our
+/// chain or hasn't arrived yet - such a block is conservatively treated as height 0, i.e. as
+/// far as possible below our tip, so it's routed onto the ancient lane whenever the current
+/// height exceeds `ANCIENT_BLOCK_THRESHOLD` rather than risking a backfill flood delaying tip
+/// traffic; on a chain no taller than the threshold it stays on the tip lane).
+fn import_lane_for(current_height: u32, parent_height: Option<u32>) -> ImportLane {
+    let block_height = parent_height.map(|height| height + 1).unwrap_or(0);
+
+    if current_height.saturating_sub(block_height) > ANCIENT_BLOCK_THRESHOLD {
+        ImportLane::Ancient
+    } else {
+        ImportLane::Tip
+    }
+}
+
+/// A block handed off to the `ImportQueue` for verification and storage insertion,
+/// along with the peer it was received from and (if any) the peer set to propagate to
+/// once the import succeeds.
+struct ImportRequest {
+    block_bytes: Vec<u8>,
+    origin: SocketAddr,
+    connected_peers: Option<HashMap<SocketAddr, PeerInfo>>,
+}
+
+/// The outcome of a single import, reported back to the task that forwards
+/// successful imports on to propagation.
+enum ImportResult {
+    /// The block was new, passed verification, was inserted into storage, and came with a
+    /// peer set to propagate it to.
+    Imported {
+        block_bytes: Vec<u8>,
+        block_hash: BlockHeaderHash,
+        origin: SocketAddr,
+        connected_peers: HashMap<SocketAddr, PeerInfo>,
+    },
+    /// There is nothing to propagate: the block was already known, malformed, or failed
+    /// verification and was dropped, or it was inserted without a peer set to propagate to
+    /// (a `SyncBlock` response).
+    Rejected,
+}
+
+/// The channel the `ImportQueue` uses to report completed imports back to `Blocks`,
+/// decoupling the verification task from the networking task that consumes the results.
+type Link = mpsc::UnboundedSender<ImportResult>;
+
+/// A handle `Blocks` holds to submit blocks to the `ImportQueue` without blocking
+/// on their verification. Blocks near the tip and historical backfill blocks are
+/// submitted on separate channels so a large backfill can't delay live blocks.
+#[derive(Clone)]
+pub(crate) struct ImportQueueService {
+    tip_sender: mpsc::Sender<ImportRequest>,
+    ancient_sender: mpsc::Sender<ImportRequest>,
+}
+
+impl ImportQueueService {
+    /// Enqueues a block for asynchronous verification and storage insertion on the
+    /// given lane.
+    ///
+    /// This applies backpressure on the caller once that lane is full, so a slow
+    /// verifier can't be outpaced by an unbounded number of in-flight blocks.
+    pub(crate) async fn enqueue(
+        &self,
+        block_bytes: Vec<u8>,
+        origin: SocketAddr,
+        connected_peers: Option<HashMap<SocketAddr, PeerInfo>>,
+        lane: ImportLane,
+    ) {
+        let request = ImportRequest {
+            block_bytes,
+            origin,
+            connected_peers,
+        };
+
+        let sender = match lane {
+            ImportLane::Tip => &self.tip_sender,
+            ImportLane::Ancient => &self.ancient_sender,
+        };
+
+        if sender.send(request).await.is_err() {
+            error!("Failed to enqueue a block for import: the import queue has shut down");
+        }
+    }
+}
+
+/// A standalone subsystem that verifies incoming blocks and inserts them into storage
+/// off of the networking task, so that verifying one peer's block doesn't hold up
+/// reading messages from the rest.
+///
+/// Tip-lane blocks are always preferred over ancient-lane ones: the ancient lane is
+/// only drained while the tip lane has nothing ready, so a long backfill never holds
+/// up freshly mined blocks or peer requests.
+struct ImportQueue {
+    environment: Environment,
+    tip_receiver: mpsc::Receiver<ImportRequest>,
+    ancient_receiver: mpsc::Receiver<ImportRequest>,
+    link: Link,
+}
+
+impl ImportQueue {
+    /// Creates a new `ImportQueue` and the `ImportQueueService` handle used to feed it.
+    fn new(environment: Environment, link: Link) -> (Self, ImportQueueService) {
+        let (tip_sender, tip_receiver) = mpsc::channel(IMPORT_QUEUE_BOUND);
+        let (ancient_sender, ancient_receiver) = mpsc::channel(IMPORT_QUEUE_BOUND);
+
+        (
+            Self {
+                environment,
+                tip_receiver,
+                ancient_receiver,
+                link,
+            },
+            ImportQueueService {
+                tip_sender,
+                ancient_sender,
+            },
+        )
+    }
+
+    /// Drains the queue, verifying and inserting each block against storage in turn,
+    /// always favoring a ready tip-lane block over an ancient-lane one.
+    async fn run(mut self) {
+        loop {
+            tokio::select! {
+                biased;
+                request = self.tip_receiver.recv() => match request {
+                    Some(request) => self.process(request, ImportLane::Tip),
+                    None => break,
+                },
+                request = self.ancient_receiver.recv() => match request {
+                    Some(request) => self.process(request, ImportLane::Ancient),
+                    None => break,
+                },
+            }
+        }
+    }
+
+    fn process(&self, request: ImportRequest, lane: ImportLane) {
+        let ImportRequest {
+            block_bytes,
+            origin,
+            connected_peers,
+        } = request;
+
+        let block_struct = match BlockStruct::deserialize(&block_bytes) {
+            Ok(block_struct) => block_struct,
+            Err(_) => {
+                let _ = self.link.send(ImportResult::Rejected);
+                return;
+            }
+        };
+
+        if self
+            .environment
+            .storage()
+            .read()
+            .block_hash_exists(&block_struct.header.get_hash())
+        {
+            let _ = self.link.send(ImportResult::Rejected);
+            return;
+        }
+
+        let is_new_block = self
+            .environment
+            .consensus_parameters()
+            .receive_block(
+                self.environment.dpc_parameters(),
+                &self.environment.storage().read(),
+                &mut self.environment.memory_pool().lock(),
+                &block_struct,
+            )
+            .is_ok();
+
+        if is_new_block && lane == ImportLane::Ancient {
+            debug!("Inserted a backfill block verified on the ancient lane");
+        }
+
+        // Propagation eligibility depends only on whether the caller supplied a peer set to
+        // propagate to, not on which lane verified the block: a competing-chain block that
+        // happens to land far below our tip is verified on the ancient lane but can still
+        // arrive as a live `Block` gossip that the network needs to hear about.
+        let result = match (is_new_block, connected_peers) {
+            (true, Some(connected_peers)) => ImportResult::Imported {
+                block_bytes,
+                block_hash: block_struct.header.get_hash(),
+                origin,
+                connected_peers,
+            },
+            _ => ImportResult::Rejected,
+        };
+
+        let _ = self.link.send(result);
+    }
+}
 
 /// A stateful component for managing the blocks for the ledger on this node server.
 #[derive(Clone)]
@@ -32,32 +549,146 @@ pub struct Blocks {
     pub(crate) environment: Environment,
     /// The outbound handler of this node server.
     outbound: Arc<Outbound>,
+    /// The handle used to submit incoming blocks to the `ImportQueue` for verification.
+    import_queue: ImportQueueService,
+    /// The inventory (block and transaction hashes) each connected peer is already
+    /// known to hold, used to avoid re-broadcasting items back to their source.
+    known_inventory: Arc<RwLock<HashMap<SocketAddr, KnownInventory>>>,
+    /// The state of an in-progress snapshot-sync restore, if one is underway.
+    snapshot_sync: Arc<RwLock<SnapshotSyncState>>,
+    /// This node's own snapshot data, served to peers and populated while restoring.
+    snapshot_store: Arc<RwLock<SnapshotStore>>,
+    /// Whether this node should attempt snapshot sync before falling back to full
+    /// block-by-block sync; set from the `--snapshot-sync` CLI flag.
+    snapshot_sync_enabled: bool,
+    /// Tracks outstanding `GetBlock` requests issued during sync, retrying the ones
+    /// a peer never answers.
+    sync_requester: Arc<SyncRequester>,
+    /// Counts consecutive empty `Headers` replies per peer while walking backward for a
+    /// common ancestor, so a peer that can't make progress isn't asked forever.
+    header_walk_empty_replies: Arc<RwLock<HashMap<SocketAddr, usize>>>,
 }
 
 impl Blocks {
     ///
     /// Creates a new instance of `Blocks`.
     ///
+    /// This spawns the `ImportQueue` task that verifies and stores blocks received
+    /// from peers, so `received_block` never blocks the networking task on consensus.
+    ///
     #[inline]
-    pub fn new(environment: Environment, outbound: Arc<Outbound>) -> Result<Self, NetworkError> {
+    pub fn new(environment: Environment, outbound: Arc<Outbound>, snapshot_sync_enabled: bool) -> Result<Self, NetworkError> {
         trace!("Instantiating the block service");
-        Ok(Self { environment, outbound })
+
+        let (import_result_sender, mut import_result_receiver) = mpsc::unbounded_channel();
+        let (import_queue, import_queue_service) = ImportQueue::new(environment.clone(), import_result_sender);
+
+        tokio::spawn(import_queue.run());
+
+        let blocks = Self {
+            environment,
+            outbound,
+            import_queue: import_queue_service,
+            known_inventory: Arc::new(RwLock::new(HashMap::new())),
+            snapshot_sync: Arc::new(RwLock::new(SnapshotSyncState::default())),
+            snapshot_store: Arc::new(RwLock::new(SnapshotStore::default())),
+            snapshot_sync_enabled,
+            sync_requester: Arc::new(SyncRequester::default()),
+            header_walk_empty_replies: Arc::new(RwLock::new(HashMap::new())),
+        };
+
+        // Forward successfully imported blocks on to propagation, off of the import queue task.
+        let propagator = blocks.clone();
+        tokio::spawn(async move {
+            while let Some(result) = import_result_receiver.recv().await {
+                if let ImportResult::Imported {
+                    block_bytes,
+                    block_hash,
+                    origin,
+                    connected_peers,
+                } = result
+                {
+                    if let Err(error) = propagator
+                        .propagate_block(block_bytes, block_hash, origin, &connected_peers)
+                        .await
+                    {
+                        warn!("Failed to propagate an imported block: {}", error);
+                    }
+                }
+            }
+        });
+
+        Ok(blocks)
     }
 
     ///
     /// Broadcasts updates with connected peers and maintains a permitted number of connected peers.
     ///
     #[inline]
-    pub async fn update(&self, sync_node: Option<SocketAddr>) -> Result<(), NetworkError> {
+    pub async fn update(
+        &self,
+        sync_node: Option<SocketAddr>,
+        connected_peers: &HashMap<SocketAddr, PeerInfo>,
+    ) -> Result<(), NetworkError> {
+        // Retry any outstanding `GetBlock` requests that a peer has failed to answer in time.
+        self.sync_requester.retry_timeouts(&self.outbound, connected_peers).await;
+
+        // Keep this node's own snapshot in step with its chain tip, so peers requesting
+        // `GetSnapshotManifest`/`GetSnapshotChunk` get real ledger state instead of nothing.
+        if self.snapshot_sync_enabled {
+            if let Err(error) = self.refresh_snapshot() {
+                warn!("Failed to refresh this node's snapshot: {}", error);
+            }
+        }
+
         // Check that this node is not a bootnode.
         if !self.environment.is_bootnode() {
             let block_locator_hashes = self.environment.storage().read().get_block_locator_hashes();
 
             if let (Some(sync_node), Ok(block_locator_hashes)) = (sync_node, block_locator_hashes) {
-                // Send a GetSync to the selected sync node.
-                self.outbound
-                    .broadcast(&Request::GetSync(sync_node, GetSync::new(block_locator_hashes)))
-                    .await;
+                // If snapshot sync is enabled, ask the sync node for a manifest and skip the
+                // full block-by-block sync below while waiting for it and while its chunks are
+                // restored. Full sync resumes once the restore completes, or once the manifest
+                // wait exceeds `SNAPSHOT_MANIFEST_TIMEOUT`; a restore whose chunks stop arriving
+                // does not fall back.
+                let mut send_get_sync = true;
+
+                if self.snapshot_sync_enabled {
+                    let mut snapshot_sync = self.snapshot_sync.write();
+
+                    if snapshot_sync.manifest.is_none() {
+                        if !snapshot_sync.requested {
+                            snapshot_sync.requested = true;
+                            snapshot_sync.requested_at = Some(Instant::now());
+                            snapshot_sync.sync_node = Some(sync_node);
+                            drop(snapshot_sync);
+
+                            self.outbound
+                                .broadcast(&Request::GetSnapshotManifest(sync_node, GetSnapshotManifest))
+                                .await;
+
+                            send_get_sync = false;
+                        } else {
+                            // Still waiting on a manifest; only fall back to full sync once
+                            // that wait has stalled for too long.
+                            send_get_sync = snapshot_sync
+                                .requested_at
+                                .map(|requested_at| requested_at.elapsed() > SNAPSHOT_MANIFEST_TIMEOUT)
+                                .unwrap_or(true);
+                        }
+                    } else if !snapshot_sync.is_complete() {
+                        // A manifest is in hand and chunks are still being restored; don't
+                        // also replay the same range block-by-block in the meantime.
+                        send_get_sync = false;
+                    }
+                }
+
+                if send_get_sync {
+                    // Send a GetSync to the selected sync node.
+                    self.outbound
+                        .broadcast(&Request::GetSync(sync_node, GetSync::new(block_locator_hashes)))
+                        .await;
+                }
             } else {
                 // If no sync node is available, wait until peers have been established.
                 info!("No sync node is registered, blocks could not be synced");
@@ -75,51 +706,145 @@ impl Blocks {
         self.environment.local_address().unwrap() // the address must be known by now
     }
 
-    /// Broadcast block to connected peers
+    /// Records that `remote_address` is now known to hold `item` (a block or
+    /// transaction hash), so it won't be re-offered the same item later.
+    fn mark_known(&self, remote_address: SocketAddr, item: Vec<u8>) {
+        self.known_inventory
+            .write()
+            .entry(remote_address)
+            .or_default()
+            .insert(item);
+    }
+
+    /// Returns `true` if `remote_address` is already known to hold `item`.
+    fn is_known(&self, remote_address: &SocketAddr, item: &[u8]) -> bool {
+        self.known_inventory
+            .read()
+            .get(remote_address)
+            .map(|inventory| inventory.contains(item))
+            .unwrap_or(false)
+    }
+
+    /// Broadcast block to connected peers that aren't already known to have it.
     pub async fn propagate_block(
         &self,
         block_bytes: Vec<u8>,
+        block_hash: BlockHeaderHash,
         block_miner: SocketAddr,
         connected_peers: &HashMap<SocketAddr, PeerInfo>,
     ) -> Result<(), NetworkError> {
-        debug!("Propagating a block to peers");
-
         let local_address = self.local_address();
+        let item = block_hash.0.to_vec();
+
+        // The miner (or the peer we received it from) obviously already has it.
+        self.mark_known(block_miner, item.clone());
+
+        let mut sent = 0;
         for remote_address in connected_peers.keys() {
-            if *remote_address != block_miner && *remote_address != local_address {
-                // Broadcast a `Block` message to the connected peer.
-                self.outbound
-                    .broadcast(&Request::Block(*remote_address, Block::new(block_bytes.clone())))
-                    .await;
+            if *remote_address == block_miner || *remote_address == local_address {
+                continue;
+            }
+
+            if self.is_known(remote_address, &item) {
+                continue;
             }
+
+            // Broadcast a `Block` message to the connected peer.
+            self.outbound
+                .broadcast(&Request::Block(*remote_address, Block::new(block_bytes.clone())))
+                .await;
+
+            self.mark_known(*remote_address, item.clone());
+            sent += 1;
         }
 
+        debug!(
+            "Propagated a block to {} of {} connected peers (rest already had it)",
+            sent,
+            connected_peers.len()
+        );
+
         Ok(())
     }
 
-    /// Broadcast transaction to connected peers
+    /// Broadcast transaction to connected peers, fanning out the full transaction to only a
+    /// random square-root-sized subset of eligible peers to bound gossip amplification, and
+    /// sending a lightweight `TransactionAnnounce` to the rest so every peer still learns of it.
     pub(crate) async fn propagate_transaction(
         &self,
         transaction_bytes: Vec<u8>,
+        transaction_id: Vec<u8>,
         transaction_sender: SocketAddr,
         connected_peers: &HashMap<SocketAddr, PeerInfo>,
     ) -> Result<(), NetworkError> {
-        debug!("Propagating a transaction to peers");
-
         let local_address = self.local_address();
 
-        for remote_address in connected_peers.keys() {
-            if *remote_address != transaction_sender && *remote_address != local_address {
-                // Broadcast a `Transaction` message to the connected peer.
-                self.outbound
-                    .broadcast(&Request::Transaction(
-                        *remote_address,
-                        Transaction::new(transaction_bytes.clone()),
-                    ))
-                    .await;
-            }
+        self.mark_known(transaction_sender, transaction_id.clone());
+        self.mark_known(local_address, transaction_id.clone());
+
+        let mut eligible: Vec<SocketAddr> = connected_peers
+            .keys()
+            .copied()
+            .filter(|remote_address| *remote_address != transaction_sender && *remote_address != local_address)
+            .filter(|remote_address| !self.is_known(remote_address, &transaction_id))
+            .collect();
+
+        let fanout = fanout_size(eligible.len());
+        eligible.shuffle(&mut rand::thread_rng());
+        let announced = eligible.split_off(fanout.min(eligible.len()));
+
+        for remote_address in &eligible {
+            // Broadcast a `Transaction` message to the connected peer.
+            self.outbound
+                .broadcast(&Request::Transaction(
+                    *remote_address,
+                    Transaction::new(transaction_bytes.clone()),
+                ))
+                .await;
+
+            self.mark_known(*remote_address, transaction_id.clone());
+        }
+
+        for remote_address in &announced {
+            // Let the rest of the network know the transaction exists without paying the
+            // bandwidth cost of sending it in full; a peer that doesn't already have it can
+            // follow up with a `GetMemoryPool` to fetch it.
+            self.outbound
+                .broadcast(&Request::TransactionAnnounce(
+                    *remote_address,
+                    TransactionAnnounce::new(transaction_id.clone()),
+                ))
+                .await;
+
+            self.mark_known(*remote_address, transaction_id.clone());
+        }
+
+        debug!(
+            "Propagated a transaction to {} of {} connected peers (announced to the other {})",
+            eligible.len(),
+            connected_peers.len(),
+            announced.len()
+        );
+
+        Ok(())
+    }
+
+    /// A peer has announced that it holds a transaction we may not have yet; if it's new to us,
+    /// ask the peer for its memory pool contents to pull it in.
+    pub(crate) async fn received_transaction_announce(
+        &self,
+        remote_address: SocketAddr,
+        message: TransactionAnnounce,
+    ) -> Result<(), NetworkError> {
+        self.mark_known(remote_address, message.transaction_id.clone());
+
+        let local_address = self.local_address();
+        if self.is_known(&local_address, &message.transaction_id) {
+            return Ok(());
         }
 
+        self.outbound.broadcast(&Request::GetMemoryPool(remote_address)).await;
+
         Ok(())
     }
 
@@ -145,6 +870,10 @@ impl Blocks {
                 return Ok(());
             }
 
+            // Captured before `tx` moves into the entry below, so it's available whether or
+            // not this transaction turns out to be new to our mempool.
+            let transaction_id = tx.transaction_id().ok().map(|id| id.to_vec());
+
             let entry = Entry::<Tx> {
                 size_in_bytes: transaction.bytes.len(),
                 transaction: tx,
@@ -152,12 +881,21 @@ impl Blocks {
 
             let insertion = self.environment.memory_pool().lock().insert(&storage.read(), entry);
 
-            if let Ok(inserted) = insertion {
-                if inserted.is_some() {
+            match insertion {
+                Ok(Some(txid)) => {
                     info!("Transaction added to memory pool.");
-                    self.propagate_transaction(transaction.bytes, source, &connected_peers)
+                    self.propagate_transaction(transaction.bytes, txid, source, &connected_peers)
                         .await?;
                 }
+                Ok(None) => {
+                    // We already had this transaction, so propagate_transaction (which marks
+                    // ourselves known) never ran for it; mark it ourselves so a later
+                    // TransactionAnnounce for it doesn't trigger a needless GetMemoryPool pull.
+                    if let Some(transaction_id) = transaction_id {
+                        self.mark_known(self.local_address(), transaction_id);
+                    }
+                }
+                Err(_) => {}
             }
         }
 
@@ -165,6 +903,10 @@ impl Blocks {
     }
 
     /// A peer has sent us a new block to process.
+    ///
+    /// This only performs a cheap structural check before handing the block off to the
+    /// `ImportQueue`; verification against consensus and storage happens there, off of
+    /// this task, so a slow or malicious peer can't stall message processing for everyone.
     #[inline]
     pub(crate) async fn received_block(
         &self,
@@ -179,44 +921,40 @@ impl Blocks {
             hex::encode(block_struct.header.get_hash().0)
         );
 
-        // Verify the block and insert it into the storage.
-        if !self
-            .environment
-            .storage()
-            .read()
-            .block_hash_exists(&block_struct.header.get_hash())
-        {
-            let is_new_block = self
-                .environment
-                .consensus_parameters()
-                .receive_block(
-                    self.environment.dpc_parameters(),
-                    &self.environment.storage().read(),
-                    &mut self.environment.memory_pool().lock(),
-                    &block_struct,
-                )
-                .is_ok();
-
-            // This is a new block, send it to our peers.
-            if let Some(connected_peers) = connected_peers {
-                if is_new_block {
-                    self.propagate_block(block.data, remote_address, &connected_peers)
-                        .await?;
-                }
-            } else {
-                /* TODO (howardwu): Implement this.
-                {
-                    sync_manager.clear_pending().await;
+        // A `connected_peers` of `None` means this arrived as a `SyncBlock` response to one
+        // of our own `GetBlock` requests; it has now arrived, so stop tracking it for retries.
+        if connected_peers.is_none() {
+            self.sync_requester.resolve(&block_struct.header.get_hash());
+        }
 
-                    if sync_manager.sync_state != SyncState::Idle {
-                        // We are currently syncing with a node, ask for the next block.
-                        sync_manager.increment().await?;
-                    }
-                }
-                */
-            }
+        // Skip blocks we already have before even bothering the import queue.
+        let storage = self.environment.storage().read();
+        if storage.block_hash_exists(&block_struct.header.get_hash()) {
+            return Ok(());
         }
 
+        // Blocks far below our current height are historical backfill: route them onto
+        // the ancient lane so they never delay verification of blocks near the tip.
+        let parent_height = storage.get_block_number(&block_struct.header.previous_block_hash).ok();
+        let current_height = storage.get_current_block_height();
+        drop(storage);
+
+        let lane = import_lane_for(current_height, parent_height);
+
+        self.import_queue
+            .enqueue(block.data, remote_address, connected_peers, lane)
+            .await;
+
+        // TODO (howardwu): Implement this.
+        // {
+        //     sync_manager.clear_pending().await;
+        //
+        //     if sync_manager.sync_state != SyncState::Idle {
+        //         // We are currently syncing with a node, ask for the next block.
+        //         sync_manager.increment().await?;
+        //     }
+        // }
+
         Ok(())
     }
 
@@ -330,20 +1068,473 @@ impl Blocks {
     }
 
     /// A peer has sent us their chain state.
-    pub(crate) async fn received_sync(&self, remote_address: SocketAddr, message: Sync) -> Result<(), NetworkError> {
+    pub(crate) async fn received_sync(
+        &self,
+        remote_address: SocketAddr,
+        message: Sync,
+        peer_block_height: u32,
+    ) -> Result<(), NetworkError> {
         let block_hashes = message.block_hashes;
 
-        // If empty sync is no-op as chain states match
-        if !block_hashes.is_empty() {
-            // GetBlocks for each block hash: fire and forget, relying on block locator hashes to
-            // detect missing blocks and divergence in chain for now.
-            for hash in block_hashes {
+        if block_hashes.is_empty() {
+            // An empty `Sync` normally means our chain states already match. If our heights
+            // actually differ, the peer shares no recent locator hash with us and has
+            // diverged; walk its header chain backward from its tip to find the last
+            // block hash we still have in common.
+            let current_height = self.environment.storage().read().get_current_block_height();
+
+            if peer_block_height != current_height {
+                warn!(
+                    "{} reports height {} but returned no blocks to sync at height {}; chains may have diverged",
+                    remote_address, peer_block_height, current_height
+                );
+
                 self.outbound
-                    .broadcast(&Request::GetBlock(remote_address, GetBlock::new(hash)))
+                    .broadcast(&Request::GetHeaders(remote_address, GetHeaders::new(peer_block_height)))
                     .await;
             }
+
+            return Ok(());
+        }
+
+        // GetBlock for each hash, tracked by the `SyncRequester` so a peer that drops
+        // a request gets retried against someone else instead of stalling sync.
+        for hash in block_hashes {
+            self.sync_requester.track(hash.clone(), remote_address);
+            self.outbound
+                .broadcast(&Request::GetBlock(remote_address, GetBlock::new(hash)))
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// A peer has requested our header hashes, walking backward from `from_height`,
+    /// so it can locate the point where our chains last agreed.
+    pub(crate) async fn received_get_headers(
+        &self,
+        remote_address: SocketAddr,
+        message: GetHeaders,
+    ) -> Result<(), NetworkError> {
+        let storage = self.environment.storage().read();
+
+        let mut header_hashes = vec![];
+        let mut height = message.from_height;
+
+        loop {
+            match storage.get_block_hash(height) {
+                Ok(hash) => header_hashes.push(hash),
+                Err(_) => break,
+            }
+
+            if height == 0 || header_hashes.len() as u32 >= HEADER_WALK_BATCH {
+                break;
+            }
+            height -= 1;
+        }
+
+        self.outbound
+            .broadcast(&Request::Headers(
+                remote_address,
+                Headers::new(message.from_height, header_hashes),
+            ))
+            .await;
+
+        Ok(())
+    }
+
+    /// A peer has sent us a batch of its header hashes, walking backward from its tip.
+    /// Find the first one we recognize - the common ancestor of our two chains - and
+    /// request the divergent range (everything above it) directly from the peer.
+    pub(crate) async fn received_headers(&self, remote_address: SocketAddr, message: Headers) -> Result<(), NetworkError> {
+        let common_ancestor_index = {
+            let storage = self.environment.storage().read();
+            message
+                .header_hashes
+                .iter()
+                .position(|hash| storage.get_block_number(hash).is_ok())
+        };
+
+        match common_ancestor_index {
+            Some(index) => {
+                // Found a recognized header; reset this peer's stall counter since it just
+                // made progress.
+                self.header_walk_empty_replies.write().remove(&remote_address);
+
+                // `header_hashes` is ordered from the tip down to the root, so everything
+                // before the common ancestor is the peer's divergent range; fetch it
+                // directly rather than waiting for another locator round to notice it.
+                for hash in message.header_hashes[..index].iter().rev() {
+                    self.sync_requester.track(hash.clone(), remote_address);
+                    self.outbound
+                        .broadcast(&Request::GetBlock(remote_address, GetBlock::new(hash.clone())))
+                        .await;
+                }
+            }
+            None => {
+                // None of the offered headers are recognized yet; keep walking backward
+                // from just before the oldest one in this batch.
+                let next_from_height = next_header_walk_height(message.from_height, message.header_hashes.len());
+
+                if message.header_hashes.is_empty() {
+                    // An empty reply makes no progress (`next_from_height` stays put), so
+                    // repeating the same request relies entirely on the peer eventually
+                    // answering differently. Cap how many times we'll ask before giving up
+                    // on this peer rather than repeating the identical request forever.
+                    let mut empty_replies = self.header_walk_empty_replies.write();
+                    let attempts = empty_replies.entry(remote_address).or_insert(0);
+                    *attempts += 1;
+
+                    if *attempts > HEADER_WALK_MAX_EMPTY_REPLIES {
+                        warn!(
+                            "Peer {} gave {} empty Headers replies in a row; giving up on the header walk with it",
+                            remote_address, *attempts
+                        );
+                        empty_replies.remove(&remote_address);
+                        return Ok(());
+                    }
+                } else {
+                    self.header_walk_empty_replies.write().remove(&remote_address);
+                }
+
+                if next_from_height > 0 {
+                    self.outbound
+                        .broadcast(&Request::GetHeaders(remote_address, GetHeaders::new(next_from_height)))
+                        .await;
+                }
+            }
         }
 
         Ok(())
     }
+
+    /// A peer has requested our latest snapshot manifest.
+    pub(crate) async fn received_get_snapshot_manifest(&self, remote_address: SocketAddr) -> Result<(), NetworkError> {
+        if let Some(manifest) = self.snapshot_store.read().manifest() {
+            // Broadcast a `SnapshotManifest` message to the connected peer.
+            self.outbound
+                .broadcast(&Request::SnapshotManifest(remote_address, manifest))
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// A peer has sent us their snapshot manifest; start pulling its chunks from peers in parallel,
+    /// round-robining requests across every connected peer instead of hammering the one that sent
+    /// the manifest.
+    pub(crate) async fn received_snapshot_manifest(
+        &self,
+        remote_address: SocketAddr,
+        manifest: SnapshotManifest,
+        connected_peers: &HashMap<SocketAddr, PeerInfo>,
+    ) -> Result<(), NetworkError> {
+        if !self.snapshot_sync_enabled {
+            return Ok(());
+        }
+
+        {
+            let mut snapshot_sync = self.snapshot_sync.write();
+            if snapshot_sync.manifest.is_some() {
+                // Already restoring from an earlier manifest.
+                return Ok(());
+            }
+            snapshot_sync.sync_node = Some(remote_address);
+            snapshot_sync.manifest = Some(manifest.clone());
+        }
+
+        info!(
+            "Received a snapshot manifest for block {} ({} chunks); restoring in parallel",
+            manifest.block_height,
+            manifest.chunk_hashes.len()
+        );
+
+        // Spread chunk requests round-robin across every peer we're connected to (falling back to
+        // the manifest's source if it's our only peer), so restoring a snapshot doesn't bottleneck
+        // on a single node's upload bandwidth.
+        let mut peers: Vec<SocketAddr> = connected_peers.keys().copied().collect();
+        if !peers.contains(&remote_address) {
+            peers.push(remote_address);
+        }
+
+        for (index, chunk_hash) in manifest.chunk_hashes.into_iter().enumerate() {
+            let target = peers[index % peers.len()];
+            self.outbound
+                .broadcast(&Request::GetSnapshotChunk(target, GetSnapshotChunk::new(chunk_hash)))
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// A peer has requested a snapshot chunk.
+    pub(crate) async fn received_get_snapshot_chunk(
+        &self,
+        remote_address: SocketAddr,
+        message: GetSnapshotChunk,
+    ) -> Result<(), NetworkError> {
+        if let Some(data) = self.snapshot_store.read().chunk(&message.chunk_hash) {
+            // Broadcast a `SnapshotChunk` message to the connected peer.
+            self.outbound
+                .broadcast(&Request::SnapshotChunk(
+                    remote_address,
+                    SnapshotChunk::new(message.chunk_hash, data),
+                ))
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// A peer has sent us a snapshot chunk; verify it against the manifest before applying it.
+    pub(crate) async fn received_snapshot_chunk(&self, message: SnapshotChunk) -> Result<(), NetworkError> {
+        let is_expected = self
+            .snapshot_sync
+            .read()
+            .manifest
+            .as_ref()
+            .map(|manifest| manifest.chunk_hashes.contains(&message.chunk_hash))
+            .unwrap_or(false);
+
+        if !is_expected || hash_snapshot_chunk(&message.data) != message.chunk_hash {
+            warn!("Received a snapshot chunk that doesn't match its advertised hash, dropping it");
+            return Ok(());
+        }
+
+        self.snapshot_store
+            .write()
+            .apply_chunk(message.chunk_hash.clone(), message.data.clone());
+
+        let completed_manifest = {
+            let mut snapshot_sync = self.snapshot_sync.write();
+            snapshot_sync.restored_chunks.insert(message.chunk_hash);
+
+            if snapshot_sync.is_complete() {
+                snapshot_sync.manifest.clone()
+            } else {
+                None
+            }
+        };
+
+        if let Some(manifest) = completed_manifest {
+            info!(
+                "Snapshot restored up to block {} ({:?}); applying it to storage and switching to normal block sync",
+                manifest.block_height,
+                hex::encode(manifest.block_hash.0)
+            );
+
+            self.apply_restored_snapshot(&manifest);
+        }
+
+        Ok(())
+    }
+
+    /// Applies every block in a completed snapshot restore to storage, in height order, via the
+    /// same consensus path normal block import uses. Safe to call more than once: blocks already
+    /// present in storage are skipped, so a duplicate or late-arriving chunk can't double-apply.
+    fn apply_restored_snapshot(&self, manifest: &SnapshotManifest) {
+        let snapshot_store = self.snapshot_store.read();
+
+        for chunk_hash in &manifest.chunk_hashes {
+            let data = match snapshot_store.chunk(chunk_hash) {
+                Some(data) => data,
+                None => continue,
+            };
+
+            let block_struct = match BlockStruct::deserialize(&data) {
+                Ok(block_struct) => block_struct,
+                Err(_) => continue,
+            };
+
+            let storage = self.environment.storage();
+
+            if storage.read().block_hash_exists(&block_struct.header.get_hash()) {
+                continue;
+            }
+
+            let _ = self.environment.consensus_parameters().receive_block(
+                self.environment.dpc_parameters(),
+                &storage.read(),
+                &mut self.environment.memory_pool().lock(),
+                &block_struct,
+            );
+        }
+    }
+
+    /// Rebuilds this node's own snapshot from current ledger state if it's missing or stale, so
+    /// `received_get_snapshot_manifest` has something real to serve to peers. Each chunk is a
+    /// single serialized block; the manifest lists their hashes in height order from genesis to
+    /// the current tip.
+    fn refresh_snapshot(&self) -> Result<(), NetworkError> {
+        let storage = self.environment.storage().read();
+        let current_height = storage.get_current_block_height();
+
+        let is_stale = self
+            .snapshot_store
+            .read()
+            .manifest()
+            .map(|manifest| manifest.block_height != current_height)
+            .unwrap_or(true);
+
+        if !is_stale {
+            return Ok(());
+        }
+
+        let tip_hash = storage.get_block_hash(current_height)?;
+
+        let mut chunk_hashes = Vec::with_capacity(current_height as usize + 1);
+        let mut chunks = HashMap::with_capacity(current_height as usize + 1);
+
+        for height in 0..=current_height {
+            let hash = storage.get_block_hash(height)?;
+            let block = storage.get_block(&hash)?;
+            let data = block.serialize()?;
+            let chunk_hash = hash_snapshot_chunk(&data);
+
+            chunk_hashes.push(chunk_hash.clone());
+            chunks.insert(chunk_hash, data);
+        }
+
+        let mut snapshot_store = self.snapshot_store.write();
+        snapshot_store.manifest = Some(SnapshotManifest::new(current_height, tip_hash, chunk_hashes));
+        snapshot_store.chunks = chunks;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_inventory_evicts_oldest_entry_past_capacity() {
+        let mut inventory = KnownInventory::default();
+
+        for i in 0..KNOWN_INVENTORY_CAPACITY {
+            inventory.insert((i as u32).to_le_bytes().to_vec());
+        }
+        assert!(inventory.contains(&0u32.to_le_bytes()));
+
+        // One more insert past capacity should evict the oldest (item 0).
+        inventory.insert((KNOWN_INVENTORY_CAPACITY as u32).to_le_bytes().to_vec());
+
+        assert!(!inventory.contains(&0u32.to_le_bytes()));
+        assert!(inventory.contains(&1u32.to_le_bytes()));
+        assert!(inventory.contains(&(KNOWN_INVENTORY_CAPACITY as u32).to_le_bytes()));
+    }
+
+    #[test]
+    fn known_inventory_reinserting_known_item_does_not_evict() {
+        let mut inventory = KnownInventory::default();
+        inventory.insert(vec![1]);
+        inventory.insert(vec![1]);
+
+        assert!(inventory.contains(&[1]));
+        assert_eq!(inventory.order.len(), 1);
+    }
+
+    #[test]
+    fn fanout_size_is_bounded_by_sqrt_and_at_least_one() {
+        assert_eq!(fanout_size(0), 1);
+        assert_eq!(fanout_size(1), 1);
+        assert_eq!(fanout_size(4), 2);
+        assert_eq!(fanout_size(10), 4);
+        assert_eq!(fanout_size(100), 10);
+    }
+
+    #[test]
+    fn snapshot_sync_state_is_complete_once_every_chunk_is_restored() {
+        let manifest = SnapshotManifest::new(10, BlockHeaderHash([0; 32]), vec![vec![1], vec![2]]);
+        let mut state = SnapshotSyncState {
+            manifest: Some(manifest),
+            ..SnapshotSyncState::default()
+        };
+        assert!(!state.is_complete());
+
+        state.restored_chunks.insert(vec![1]);
+        assert!(!state.is_complete());
+
+        state.restored_chunks.insert(vec![2]);
+        assert!(state.is_complete());
+    }
+
+    #[test]
+    fn snapshot_sync_state_without_manifest_is_not_complete() {
+        assert!(!SnapshotSyncState::default().is_complete());
+    }
+
+    #[test]
+    fn sync_requester_track_and_resolve_round_trip() {
+        let requester = SyncRequester::default();
+        let hash = BlockHeaderHash([1; 32]);
+        let peer: SocketAddr = "127.0.0.1:4141".parse().unwrap();
+
+        requester.track(hash.clone(), peer);
+        assert!(requester.outstanding.read().contains_key(&hash));
+
+        requester.resolve(&hash);
+        assert!(!requester.outstanding.read().contains_key(&hash));
+    }
+
+    #[test]
+    fn outstanding_request_is_exhausted_at_max_attempts() {
+        let mut request = OutstandingRequest {
+            peer: "127.0.0.1:4141".parse().unwrap(),
+            sent_at: Instant::now(),
+            attempts: SYNC_REQUEST_MAX_ATTEMPTS - 1,
+        };
+        assert!(!request.is_exhausted());
+
+        request.attempts = SYNC_REQUEST_MAX_ATTEMPTS;
+        assert!(request.is_exhausted());
+    }
+
+    #[test]
+    fn next_header_walk_height_holds_steady_on_empty_reply() {
+        // An empty Headers reply must not advance the walk, which is exactly why callers
+        // need to cap retries rather than relying on this height to change.
+        assert_eq!(next_header_walk_height(500, 0), 500);
+    }
+
+    #[test]
+    fn next_header_walk_height_steps_back_by_batch_size() {
+        assert_eq!(next_header_walk_height(500, 128), 372);
+    }
+
+    #[test]
+    fn next_header_walk_height_saturates_at_zero() {
+        assert_eq!(next_header_walk_height(10, 128), 0);
+    }
+
+    #[test]
+    fn import_lane_for_tip_block_near_current_height() {
+        assert_eq!(import_lane_for(1_000, Some(999)), ImportLane::Tip);
+    }
+
+    #[test]
+    fn import_lane_for_block_exactly_at_ancient_threshold_is_tip() {
+        // The block sits one above its parent, so this block is exactly the threshold below the tip.
+        assert_eq!(import_lane_for(1_000, Some(1_000 - ANCIENT_BLOCK_THRESHOLD - 1)), ImportLane::Tip);
+    }
+
+    #[test]
+    fn import_lane_for_block_past_ancient_threshold_is_ancient() {
+        assert_eq!(
+            import_lane_for(1_000, Some(1_000 - ANCIENT_BLOCK_THRESHOLD - 2)),
+            ImportLane::Ancient
+        );
+    }
+
+    #[test]
+    fn import_lane_for_unknown_parent_falls_back_to_ancient_above_threshold() {
+        // An unresolvable parent is treated as height 0, so it lands on the ancient lane
+        // whenever the current height exceeds the threshold.
+        assert_eq!(import_lane_for(ANCIENT_BLOCK_THRESHOLD + 1, None), ImportLane::Ancient);
+    }
+
+    #[test]
+    fn import_lane_for_unknown_parent_is_tip_on_a_short_chain() {
+        assert_eq!(import_lane_for(ANCIENT_BLOCK_THRESHOLD, None), ImportLane::Tip);
+    }
 }